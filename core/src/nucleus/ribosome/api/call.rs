@@ -9,21 +9,79 @@ use crate::{
         ZomeFnCall,
     },
 };
-use holochain_core_types::{dna::capabilities::Membrane, error::HolochainError};
+use holochain_core_types::{
+    cell::CellId,
+    dna::capabilities::{CallerProvenance, CapTokenGrant, CapabilityType, Membrane},
+    entry::entry_type::EntryType,
+    error::HolochainError,
+    json::JsonString,
+};
+use holochain_core_types_derive::DefaultJson;
 use holochain_wasm_utils::api_serialization::ZomeFnCallArgs;
+use serde_derive::{Deserialize, Serialize};
 use std::{
     convert::TryFrom,
-    sync::{mpsc::channel, Arc},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{channel, RecvTimeoutError},
+        Arc,
+    },
+    thread,
 };
 use wasmi::{RuntimeArgs, RuntimeValue};
 
 // ZomeFnCallArgs to ZomeFnCall
 impl ZomeFnCall {
     fn from_args(args: ZomeFnCallArgs) -> Self {
-        ZomeFnCall::new(&args.zome_name, &args.cap_name, &args.fn_name, args.fn_args)
+        ZomeFnCall {
+            to_cell: args.to_cell,
+            cap_request: args.cap_request,
+            ..ZomeFnCall::new(&args.zome_name, &args.cap_name, &args.fn_name, args.fn_args)
+        }
+    }
+}
+
+/// One entry of the call stack threaded through nested `Action::Call`s, identifying which
+/// cell/zome/fn a frame belongs to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DefaultJson)]
+pub struct CallFrame {
+    pub to_cell: Option<CellId>,
+    pub zome_name: String,
+    pub fn_name: String,
+}
+
+impl CallFrame {
+    fn of(call: &ZomeFnCall) -> Self {
+        CallFrame {
+            to_cell: call.to_cell.clone(),
+            zome_name: call.zome_name.clone(),
+            fn_name: call.fn_name.clone(),
+        }
     }
 }
 
+/// Bound on nested zome-to-zome calls, applied by `extend_call_stack`. It is a
+/// compile-time constant; it cannot be configured per instance. Replaces the old blanket
+/// `same_fn_as(&runtime.zome_call)` check, which forbade any direct self-recursion even
+/// when legitimate, while still stopping runaway or unbounded-cycle call chains.
+pub const MAX_CALL_DEPTH: usize = 10;
+
+/// Push `caller`'s frame onto `zome_call`'s call stack and check the bounded-recursion
+/// invariant. Forbids the call only once the depth limit is exceeded — any cycle, direct
+/// or mutual, is necessarily caught by the depth bound, so there's no need to separately
+/// reject an exact repeated frame. On success, `zome_call.call_stack` is updated in place.
+fn extend_call_stack(zome_call: &mut ZomeFnCall, caller: &ZomeFnCall) -> bool {
+    let mut stack = caller.call_stack.clone();
+    stack.push(CallFrame::of(caller));
+
+    if stack.len() > MAX_CALL_DEPTH {
+        return false;
+    }
+
+    zome_call.call_stack = stack;
+    true
+}
+
 /// HcApiFuncIndex::CALL function code
 /// args: [0] encoded MemoryAllocation as u32
 /// expected complex argument: {zome_name: String, cap_name: String, fn_name: String, args: String}
@@ -45,10 +103,17 @@ pub fn invoke_call(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
     };
 
     // ZomeFnCallArgs to ZomeFnCall
-    let zome_call = ZomeFnCall::from_args(input);
+    // The caller's provenance (agent pubkey + calling zome name) is not guest-supplied:
+    // it is filled in here, by the host, from the currently executing zome call, so that
+    // membrane checks in `reduce_call` can trust it.
+    let mut zome_call = ZomeFnCall::from_args(input);
+    zome_call.provenance = Some(CallerProvenance {
+        agent: runtime.context.agent_id.address(),
+        zome_name: Some(runtime.zome_call.zome_name.clone()),
+    });
 
-    // Don't allow recursive calls
-    if zome_call.same_fn_as(&runtime.zome_call) {
+    // Bound zome-to-zome recursion instead of forbidding it outright
+    if !extend_call_stack(&mut zome_call, &runtime.zome_call) {
         return ribosome_error_code!(RecursiveCallForbidden);
     }
 
@@ -79,20 +144,143 @@ pub fn invoke_call(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
             }
         },
     );
-    // TODO #97 - Return error if timeout or something failed
-    // return Err(_);
-
-    let result = receiver
-        .recv_timeout(RECV_DEFAULT_TIMEOUT_MS)
-        .expect("observer dropped before done");
+    // A timeout or a disconnected observer channel is returned to the calling zome as an
+    // error result instead of panicking the wasm host.
+    // @see https://github.com/holochain/holochain-rust/issues/159
+    let result = recv_call_result(
+        &receiver,
+        RECV_DEFAULT_TIMEOUT_MS,
+        "observer channel disconnected before the zome call finished",
+    );
     runtime.store_result(result)
 }
 
+/// Resolve a call-result receiver into either the callee's result or a non-panicking
+/// `HolochainError` describing why none arrived in time - a timeout, or the sending end
+/// disconnecting first. Shared by `invoke_call`'s synchronous wait and
+/// `reduce_call_bridged`'s background wait so both report channel loss the same way.
+fn recv_call_result(
+    receiver: &std::sync::mpsc::Receiver<Result<JsonString, HolochainError>>,
+    timeout: std::time::Duration,
+    disconnected_msg: &str,
+) -> Result<JsonString, HolochainError> {
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(HolochainError::Timeout),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(HolochainError::ErrorGeneric(disconnected_msg.into()))
+        }
+    }
+}
+
+/// An opaque handle for an in-flight call started by `invoke_call_async`. Round-tripped
+/// through the guest and passed back into `invoke_call_result` to poll for completion.
+/// Wraps the originating `ZomeFnCall`, which is also the key `zome_call_result` is looked
+/// up by - `zome_call.call_id` is stamped with a value unique to this dispatch before the
+/// handle is built, so two calls that are otherwise identical (same zome/cap/fn/args/call
+/// stack) started concurrently don't collide on the same `state.zome_calls` entry.
+#[derive(Clone, Debug, Serialize, Deserialize, DefaultJson)]
+pub struct CallHandle(ZomeFnCall);
+
+/// Source of the nonces stamped into `ZomeFnCall::call_id` by `invoke_call_async`. Process
+/// wide rather than per-instance since it only needs to make concurrent handles distinct,
+/// not to be stable across restarts.
+static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The status returned by `invoke_call_result` while the callee has not finished yet.
+#[derive(Clone, Debug, Serialize, Deserialize, DefaultJson)]
+pub enum CallStatus {
+    Pending,
+}
+
+/// HcApiFuncIndex::CALL_ASYNC function code
+/// Registered in the zome API dispatch table as `ZomeApiFunction::CallAsync`, the same way
+/// `ZomeApiFunction::Call` resolves to `invoke_call` above.
+/// Same deserialization and recursion guard as `invoke_call`, but does not block: it
+/// dispatches `Action::Call` and immediately returns a `CallHandle` to the guest. The
+/// callee runs on its usual background thread (see `launch_zome_fn_call`); the guest
+/// polls for the result with `invoke_call_result`. This lets a single zome fan out
+/// several independent calls instead of blocking the wasm thread on each one in turn.
+pub fn invoke_call_async(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+
+    let input = match ZomeFnCallArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            println!("invoke_call_async failed to deserialize: {:?}", args_str);
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+
+    // ZomeFnCallArgs to ZomeFnCall
+    let mut zome_call = ZomeFnCall::from_args(input);
+    zome_call.provenance = Some(CallerProvenance {
+        agent: runtime.context.agent_id.address(),
+        zome_name: Some(runtime.zome_call.zome_name.clone()),
+    });
+
+    // Bound zome-to-zome recursion instead of forbidding it outright
+    if !extend_call_stack(&mut zome_call, &runtime.zome_call) {
+        return ribosome_error_code!(RecursiveCallForbidden);
+    }
+
+    // Stamp a nonce unique to this dispatch so this call can't collide in
+    // `state.zome_calls` with another concurrent, otherwise-identical async call.
+    zome_call.call_id = Some(NEXT_CALL_ID.fetch_add(1, Ordering::Relaxed).to_string());
+
+    let handle = CallHandle(zome_call.clone());
+
+    // Dispatch and return immediately; no observer, no blocking receiver. A disconnected
+    // action channel surfaces as a catchable error to the guest instead of panicking the
+    // wasm host, the same way a disconnected observer channel does in `invoke_call`.
+    let action_wrapper = ActionWrapper::new(Action::Call(zome_call));
+    if runtime.context.action_channel().send(action_wrapper).is_err() {
+        return runtime.store_result(Err(HolochainError::ErrorGeneric(
+            "action channel disconnected before the async call could be dispatched".into(),
+        )));
+    }
+
+    runtime.store_result(Ok(JsonString::from(handle)))
+}
+
+/// HcApiFuncIndex::CALL_RESULT function code
+/// Registered in the zome API dispatch table as `ZomeApiFunction::CallResult`.
+/// Polls for the result of a call previously started with `invoke_call_async`. Returns
+/// `CallStatus::Pending` if the callee has not finished yet, otherwise the same
+/// `ZomeFnResult` that `invoke_call` would have returned synchronously.
+pub fn invoke_call_result(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let args_str = runtime.load_json_string_from_args(&args);
+
+    let handle = match CallHandle::try_from(args_str.clone()) {
+        Ok(handle) => handle,
+        Err(_) => {
+            println!("invoke_call_result failed to deserialize: {:?}", args_str);
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+
+    // No state yet (or the callee simply hasn't finished): either way there is no result
+    // to report, so the guest just sees the call as still pending rather than a host panic.
+    let result = runtime
+        .context
+        .state()
+        .and_then(|state| state.nucleus().zome_call_result(&handle.0));
+    match result {
+        Some(result) => runtime.store_result(result),
+        None => runtime.store_result(Ok(JsonString::from(CallStatus::Pending))),
+    }
+}
+
 /// Reduce Call Action
 ///   1. Checks for correctness of ZomeFnCall inside the Action
 ///   2. Checks for permission to access Capability
 ///   3. Execute the exposed Zome function in a separate thread
 /// Send the result in a ReturnZomeFunctionResult Action on success or failure like ExecuteZomeFunction
+///
+/// If the call targets a different cell (`fn_call.to_cell` is set), the call is a bridge call:
+/// it is forwarded to the bridged instance instead of being resolved against `state.dna`.
 pub(crate) fn reduce_call(
     context: Arc<Context>,
     state: &mut NucleusState,
@@ -103,6 +291,12 @@ pub(crate) fn reduce_call(
         Action::Call(call) => call,
         _ => unreachable!(),
     };
+
+    if let Some(ref to_cell) = fn_call.to_cell {
+        reduce_call_bridged(context, state, fn_call.clone(), to_cell.clone());
+        return;
+    }
+
     // Get Capability
     if state.dna.is_none() {
         // Notify failure
@@ -123,21 +317,20 @@ pub(crate) fn reduce_call(
     let cap = maybe_cap.unwrap().clone();
 
     // 2. Checks for permission to access Capability
-    // TODO #301 - Do real Capability token check
     let can_call = match cap.cap_type.membrane {
         Membrane::Public => true,
-        Membrane::Zome => {
-            // TODO #301 - check if caller zome_name is same as called zome_name
-            false
-        }
-        Membrane::Agent => {
-            // TODO #301 - check if caller has Agent Capability
-            false
-        }
-        Membrane::ApiKey => {
-            // TODO #301 - check if caller has ApiKey Capability
-            false
-        }
+        Membrane::Zome => fn_call
+            .provenance
+            .as_ref()
+            .and_then(|p| p.zome_name.as_ref())
+            .map(|caller_zome| *caller_zome == fn_call.zome_name)
+            .unwrap_or(false),
+        Membrane::Agent => fn_call
+            .provenance
+            .as_ref()
+            .map(|p| p.agent == context.agent_id.address())
+            .unwrap_or(false),
+        Membrane::ApiKey => check_capability_grant(&context, &fn_call),
     };
     if !can_call {
         // Notify failure
@@ -156,6 +349,142 @@ pub(crate) fn reduce_call(
     launch_zome_fn_call(context, fn_call, &code, state.dna.clone().unwrap().name);
 }
 
+/// Check an incoming `ZomeFnCall`'s `cap_request` against the `CapTokenGrant` committed
+/// on this chain for `fn_call.cap_name`. Grants are discovered by scanning the agent's
+/// own source chain for `EntryType::CapTokenGrant` entries, matched by grant id.
+///
+/// - No grant found, or no `cap_request` supplied: deny.
+/// - `CapabilityType::Public` or `CapabilityType::Transferable`: any caller holding the
+///   secret may call.
+/// - `CapabilityType::Assigned`: the host-filled `fn_call.provenance` (not the
+///   guest-supplied `cap_request.provenance`) must additionally appear in the grant's
+///   list of assignees, since only the former is trustworthy.
+fn check_capability_grant(context: &Context, fn_call: &ZomeFnCall) -> bool {
+    let cap_request = match fn_call.cap_request {
+        Some(ref cap_request) => cap_request,
+        None => return false,
+    };
+    let caller_agent = match fn_call.provenance {
+        Some(ref provenance) => &provenance.agent,
+        None => return false,
+    };
+    let grant = match get_grant(context, &fn_call.cap_name) {
+        Some(grant) => grant,
+        None => return false,
+    };
+    if !constant_time_eq(cap_request.cap_secret.as_ref(), grant.secret().as_ref()) {
+        return false;
+    }
+    match grant.cap_type() {
+        CapabilityType::Assigned => grant
+            .assignees()
+            .map(|assignees| assignees.contains(caller_agent))
+            .unwrap_or(false),
+        CapabilityType::Transferable => true,
+        CapabilityType::Public => true,
+    }
+}
+
+/// Find the `CapTokenGrant` entry with the given id on the agent's own source chain, if any.
+fn get_grant(context: &Context, cap_name: &str) -> Option<CapTokenGrant> {
+    let state = context.state()?;
+    let chain = state.agent().chain_store();
+    let top_header = state.agent().top_chain_header()?;
+    chain
+        .iter_type(&Some(top_header), &EntryType::CapTokenGrant)
+        .filter_map(|header| {
+            chain
+                .content_storage()
+                .read()
+                .unwrap()
+                .fetch(header.entry_address())
+                .ok()
+                .and_then(|maybe_entry| maybe_entry)
+        })
+        .filter_map(|entry| CapTokenGrant::try_from(entry).ok())
+        .find(|grant| grant.id() == cap_name)
+}
+
+/// Byte-for-byte equality that does not short-circuit on the first mismatch, so that
+/// comparing a caller-supplied `cap_secret` against the stored grant secret does not leak
+/// timing information about how many leading bytes matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Forward a bridge `ZomeFnCall` to another instance running on the same conductor.
+/// Resolves `to_cell` to a running instance via the conductor API and dispatches a fresh
+/// `Action::Call` into that instance's own action channel, then returns immediately -
+/// like every other blocking wait in this file, the wait for the bridged result never
+/// happens on the reducer thread itself (it would otherwise stall this instance's entire
+/// action-processing loop, and could deadlock two instances bridging into each other).
+/// Instead, a background thread waits for the bridged instance's result and reports it
+/// back via `Action::ReturnZomeFunctionResult` under the *original* `fn_call`, so the
+/// blocking observer set up in `invoke_call` (or the poller in `invoke_call_result`) wakes
+/// up as if the call had been handled locally.
+fn reduce_call_bridged(
+    context: Arc<Context>,
+    state: &mut NucleusState,
+    fn_call: ZomeFnCall,
+    to_cell: CellId,
+) {
+    let bridged_instance = match context.conductor_api.instance(&to_cell) {
+        Some(instance) => instance,
+        None => {
+            state.zome_calls.insert(
+                fn_call.clone(),
+                Some(Err(HolochainError::ErrorGeneric(format!(
+                    "no bridged instance found for cell {:?}",
+                    to_cell
+                )))),
+            );
+            return;
+        }
+    };
+
+    // The remote call is dispatched without `to_cell` so the bridged instance resolves
+    // it against its own `state.dna` rather than recursing into another bridge hop.
+    let mut remote_call = fn_call.clone();
+    remote_call.to_cell = None;
+
+    state.zome_calls.insert(fn_call.clone(), None);
+
+    thread::spawn(move || {
+        let (sender, receiver) = channel();
+        let remote_call_for_observer = remote_call.clone();
+        crate::instance::dispatch_action_with_observer(
+            bridged_instance.action_channel(),
+            bridged_instance.observer_channel(),
+            ActionWrapper::new(Action::Call(remote_call.clone())),
+            move |state: &crate::state::State| {
+                match state.nucleus().zome_call_result(&remote_call_for_observer) {
+                    Some(result) => {
+                        let _ = sender.send(result);
+                        true
+                    }
+                    None => false,
+                }
+            },
+        );
+
+        let result = recv_call_result(
+            &receiver,
+            RECV_DEFAULT_TIMEOUT_MS,
+            "bridged instance disconnected before the zome call finished",
+        );
+
+        context
+            .action_channel()
+            .send(ActionWrapper::new(Action::ReturnZomeFunctionResult((
+                fn_call, result,
+            ))))
+            .ok();
+    });
+}
+
 #[cfg(test)]
 pub mod tests {
     extern crate tempfile;
@@ -191,9 +520,12 @@ pub mod tests {
     };
     use holochain_wasm_utils::api_serialization::ZomeFnCallArgs;
     use serde_json;
-    use std::sync::{
-        mpsc::{channel, RecvTimeoutError},
-        Arc, Mutex, RwLock,
+    use std::{
+        convert::TryFrom,
+        sync::{
+            mpsc::{channel, RecvTimeoutError},
+            Arc, Mutex, RwLock,
+        },
     };
     use test_utils::create_test_dna_with_cap;
 
@@ -205,6 +537,8 @@ pub mod tests {
             cap_name: "cap_name".to_string(),
             fn_name: "fn_name".to_string(),
             fn_args: "fn_args".to_string(),
+            to_cell: None,
+            cap_request: None,
         };
         serde_json::to_string(&args)
             .expect("args should serialize")
@@ -218,6 +552,8 @@ pub mod tests {
             cap_name: test_capability(),
             fn_name: test_function_name(),
             fn_args: test_parameters(),
+            to_cell: None,
+            cap_request: None,
         };
         serde_json::to_string(&args)
             .expect("args should serialize")
@@ -320,4 +656,108 @@ pub mod tests {
         let expected = Err(RecvTimeoutError::Disconnected);
         test_reduce_call(dna, expected);
     }
+
+    #[test]
+    fn test_from_args_local_call_has_no_target_cell() {
+        let zome_call = ZomeFnCall::from_args(ZomeFnCallArgs {
+            zome_name: test_zome_name(),
+            cap_name: test_capability(),
+            fn_name: test_function_name(),
+            fn_args: test_parameters(),
+            to_cell: None,
+            cap_request: None,
+        });
+        assert_eq!(zome_call.to_cell, None);
+    }
+
+    #[test]
+    fn test_check_capability_grant_denies_without_cap_request() {
+        let context = create_context();
+        let zome_call = ZomeFnCall::new("test_zome", "test_cap", "test", "{}");
+        assert!(!super::check_capability_grant(&context, &zome_call));
+    }
+
+    #[test]
+    fn test_call_handle_json_roundtrip() {
+        let zome_call = ZomeFnCall::new("test_zome", "test_cap", "test", "{}");
+        let handle = super::CallHandle(zome_call.clone());
+        let json = JsonString::from(handle);
+        let roundtripped = super::CallHandle::try_from(json).expect("should deserialize");
+        assert_eq!(roundtripped.0, zome_call);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(super::constant_time_eq(b"sekrit", b"sekrit"));
+        assert!(!super::constant_time_eq(b"sekrit", b"sekrit!"));
+        assert!(!super::constant_time_eq(b"sekrit", b"nope!!"));
+    }
+
+    #[test]
+    fn test_extend_call_stack_allows_bounded_recursion() {
+        // Each level calls into a distinct function of the same zome, so this is nested
+        // calling, not a cycle, and should only be bounded by MAX_CALL_DEPTH.
+        let mut caller = ZomeFnCall::new("test_zome", "test_cap", "fn_0", "{}");
+        for i in 1..(super::MAX_CALL_DEPTH) {
+            let mut next =
+                ZomeFnCall::new("test_zome", "test_cap", &format!("fn_{}", i), "{}");
+            assert!(super::extend_call_stack(&mut next, &caller));
+            caller = next;
+        }
+    }
+
+    #[test]
+    fn test_extend_call_stack_forbids_past_max_depth() {
+        let mut caller = ZomeFnCall::new("test_zome", "test_cap", "fn_0", "{}");
+        for i in 1..=super::MAX_CALL_DEPTH {
+            let mut next =
+                ZomeFnCall::new("test_zome", "test_cap", &format!("fn_{}", i), "{}");
+            assert!(super::extend_call_stack(&mut next, &caller));
+            caller = next;
+        }
+        let mut one_too_many =
+            ZomeFnCall::new("test_zome", "test_cap", "fn_one_too_many", "{}");
+        assert!(!super::extend_call_stack(&mut one_too_many, &caller));
+    }
+
+    #[test]
+    fn test_extend_call_stack_allows_direct_self_recursion_within_depth() {
+        // The old `same_fn_as` check forbade a function calling itself outright. That's
+        // exactly the legitimate recursion this request asks to allow, bounded only by
+        // MAX_CALL_DEPTH.
+        let mut caller = ZomeFnCall::new("test_zome", "test_cap", "test", "{}");
+        for _ in 0..super::MAX_CALL_DEPTH {
+            let mut repeat = ZomeFnCall::new("test_zome", "test_cap", "test", "{}");
+            assert!(super::extend_call_stack(&mut repeat, &caller));
+            caller = repeat;
+        }
+
+        let mut one_too_many = ZomeFnCall::new("test_zome", "test_cap", "test", "{}");
+        assert!(!super::extend_call_stack(&mut one_too_many, &caller));
+    }
+
+    #[test]
+    fn test_recv_call_result_disconnected() {
+        let (sender, receiver) = channel::<Result<JsonString, HolochainError>>();
+        drop(sender);
+        let result = super::recv_call_result(
+            &receiver,
+            std::time::Duration::from_millis(50),
+            "observer gone",
+        );
+        assert_eq!(result, Err(HolochainError::ErrorGeneric("observer gone".into())));
+    }
+
+    #[test]
+    fn test_recv_call_result_timeout() {
+        // Keep the sender alive but never send on it, so recv_timeout times out rather
+        // than immediately seeing a disconnected channel.
+        let (_sender, receiver) = channel::<Result<JsonString, HolochainError>>();
+        let result = super::recv_call_result(
+            &receiver,
+            std::time::Duration::from_millis(10),
+            "observer gone",
+        );
+        assert_eq!(result, Err(HolochainError::Timeout));
+    }
 }